@@ -0,0 +1,85 @@
+//! Context management.
+//!
+//! A CUDA context holds all of the state for a device -- allocations, loaded modules, streams,
+//! and so on -- and every driver call operates against whichever context is current on the
+//! calling thread. The driver distinguishes two classes of error: non-sticky errors (such as
+//! `InvalidValue`, or a single `OutOfMemory`) are reported by the call that failed but leave the
+//! context fully usable, while sticky errors (see [`CudaError::is_sticky`]) corrupt the current
+//! context so that every subsequent call on it keeps failing until the context is destroyed and
+//! recreated.
+//!
+//! The driver itself has no call to query whether a context still has an outstanding error --
+//! once a sticky error is returned, it is simply returned again on the next call. This module
+//! tracks that for the caller: every failure produced by [`ToResult::to_result`] is fed through
+//! [`record_error`], and [`peek_error`]/[`take_error`] report back whether a sticky error is
+//! outstanding, so callers can decide between retrying the operation (non-sticky) and tearing
+//! the context down (sticky).
+//!
+//! A context can be current on more than one thread at a time, and a sticky error corrupts the
+//! context, not just the thread that observed it -- so the outstanding-error state is keyed by
+//! the context handle (via `cuCtxGetCurrent`) rather than kept per-thread, and is visible to
+//! every thread the corrupted context is current on.
+//!
+//! `peek_error`/`take_error` are free functions rather than methods on a `Context` type: this
+//! slice of cust does not yet have a constructible `Context` (no `cuCtxCreate` wrapper lives
+//! here), and a struct with no constructor and no other fields would just be dead code.
+
+use crate::error::CudaError;
+use crate::sys::{self as cuda, cudaError_enum, CUcontext};
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+static OUTSTANDING_ERRORS: Mutex<BTreeMap<usize, CudaError>> = Mutex::new(BTreeMap::new());
+
+/// Returns the context current on this thread, identified by its raw handle, or `None` if no
+/// context is current.
+///
+/// Bypasses [`ToResult`](crate::error::ToResult) deliberately: `record_error` is called from
+/// `to_result` itself, and routing this query back through it would recurse.
+fn current_context_key() -> Option<usize> {
+    let mut ctx: CUcontext = std::ptr::null_mut();
+    let status = unsafe { cuda::cuCtxGetCurrent(&mut ctx as *mut CUcontext) };
+    if status == cudaError_enum::CUDA_SUCCESS && !ctx.is_null() {
+        Some(ctx as usize)
+    } else {
+        None
+    }
+}
+
+/// Records `err` as the outstanding error for the context current on this thread, if `err` is
+/// sticky. Non-sticky errors leave the context usable and are not recorded.
+///
+/// Only the first sticky error per context is kept: once a context is corrupted, later errors
+/// are just echoes of the same underlying failure.
+pub(crate) fn record_error(err: CudaError) {
+    if !err.is_sticky() {
+        return;
+    }
+    if let Some(key) = current_context_key() {
+        let mut errors = OUTSTANDING_ERRORS.lock().unwrap();
+        errors.entry(key).or_insert(err);
+    }
+}
+
+/// Returns the outstanding sticky error for the context current on this thread, if any, without
+/// clearing it.
+///
+/// A `Some` result means the context has been corrupted by a sticky error and must be destroyed
+/// and recreated; every other call made against it will keep failing, on every thread the
+/// context is current on. A `None` result means the context is still healthy, though non-sticky
+/// errors may still have been returned by individual calls.
+pub fn peek_error() -> Option<CudaError> {
+    let key = current_context_key()?;
+    OUTSTANDING_ERRORS.lock().unwrap().get(&key).copied()
+}
+
+/// Returns and clears the outstanding sticky error for the context current on this thread, if
+/// any.
+///
+/// Clearing only resets cust's bookkeeping -- it does not repair the underlying context. Call
+/// this once you have decided to tear the context down, so that the next context created with
+/// the same underlying handle starts with a clean slate.
+pub fn take_error() -> Option<CudaError> {
+    let key = current_context_key()?;
+    OUTSTANDING_ERRORS.lock().unwrap().remove(&key)
+}
@@ -8,6 +8,7 @@
 //! errors related to previous asynchronous launches.
 
 use crate::sys::{self as cuda, cudaError_enum};
+use std::convert::TryFrom;
 use std::error::Error;
 use std::ffi::CStr;
 use std::fmt;
@@ -124,25 +125,453 @@ pub enum CudaError {
     InvalidMemoryAllocation = 100_100,
     OptixError = 100_101,
 }
+impl CudaError {
+    /// Returns `true` if this error is "sticky": once the driver reports it, the current
+    /// context is left corrupted and every subsequent call on that context keeps failing,
+    /// no matter what is called. The only way to recover from a sticky error is to destroy
+    /// and recreate the context.
+    ///
+    /// Returns `false` for "non-sticky" errors, which are reported by the call that failed
+    /// but otherwise leave the context fully usable (e.g. `InvalidValue`, or a single
+    /// `OutOfMemory` that can be retried once memory has been freed).
+    pub fn is_sticky(&self) -> bool {
+        matches!(
+            self,
+            CudaError::IllegalAddress
+                | CudaError::MisalignedAddress
+                | CudaError::IllegalInstruction
+                | CudaError::InvalidAddressSpace
+                | CudaError::InvalidProgramCounter
+                | CudaError::LaunchFailed
+                | CudaError::HardwareStackError
+                | CudaError::EccUncorrectable
+                | CudaError::NvlinkUncorrectable
+                | CudaError::AssertError
+        )
+    }
+
+    /// Returns `true` if this error may succeed on a later attempt once memory pressure has
+    /// been relieved, i.e. it is [`CudaError::OutOfMemory`].
+    ///
+    /// Used by [`crate::memory`]'s allocation retry hook to decide which failures are worth
+    /// giving a caller-supplied callback a chance to free memory and retry.
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, CudaError::OutOfMemory)
+    }
+
+    /// Maps a raw `u32` driver status code to the matching [`CudaError`] variant, without
+    /// going through `cudaError_enum`.
+    ///
+    /// `code` may be an arbitrary value from a caller cust does not control -- e.g. a status
+    /// from a newer CUDA release than these bindings cover, or outright garbage -- so this
+    /// matches on the integer directly instead of transmuting it into `cudaError_enum`
+    /// (`cudaError_enum`'s match is exhaustive with no wildcard, so transmuting a value that
+    /// is not one of its known discriminants is immediate undefined behavior). Returns `None`
+    /// for `CUDA_SUCCESS` and for any code this table does not recognize.
+    fn from_u32(code: u32) -> Option<CudaError> {
+        Some(match code {
+            1 => CudaError::InvalidValue,
+            2 => CudaError::OutOfMemory,
+            3 => CudaError::NotInitialized,
+            4 => CudaError::Deinitialized,
+            5 => CudaError::ProfilerDisabled,
+            6 => CudaError::ProfilerNotInitialized,
+            7 => CudaError::ProfilerAlreadyStarted,
+            8 => CudaError::ProfilerAlreadyStopped,
+            34 => CudaError::StubLibrary,
+            46 => CudaError::DeviceUnavailable,
+            100 => CudaError::NoDevice,
+            101 => CudaError::InvalidDevice,
+            102 => CudaError::DeviceNotLicensed,
+            200 => CudaError::InvalidImage,
+            201 => CudaError::InvalidContext,
+            202 => CudaError::ContextAlreadyCurrent,
+            205 => CudaError::MapFailed,
+            206 => CudaError::UnmapFailed,
+            207 => CudaError::ArrayIsMapped,
+            208 => CudaError::AlreadyMapped,
+            209 => CudaError::NoBinaryForGpu,
+            210 => CudaError::AlreadyAcquired,
+            211 => CudaError::NotMapped,
+            212 => CudaError::NotMappedAsArray,
+            213 => CudaError::NotMappedAsPointer,
+            214 => CudaError::EccUncorrectable,
+            215 => CudaError::UnsupportedLimit,
+            216 => CudaError::ContextAlreadyInUse,
+            217 => CudaError::PeerAccessUnsupported,
+            218 => CudaError::InvalidPtx,
+            219 => CudaError::InvalidGraphicsContext,
+            220 => CudaError::NvlinkUncorrectable,
+            221 => CudaError::JitCompilerNotFound,
+            222 => CudaError::UnsupportedPtxVersion,
+            223 => CudaError::JitCompilationDisabled,
+            224 => CudaError::UnsupportedExecAffinity,
+            225 => CudaError::UnsupportedDevSideSync,
+            300 => CudaError::InvalidSource,
+            301 => CudaError::FileNotFound,
+            302 => CudaError::SharedObjectSymbolNotFound,
+            303 => CudaError::SharedObjectInitFailed,
+            304 => CudaError::OperatingSystemError,
+            400 => CudaError::InvalidHandle,
+            401 => CudaError::IllegalState,
+            402 => CudaError::LossyQuery,
+            500 => CudaError::NotFound,
+            600 => CudaError::NotReady,
+            700 => CudaError::IllegalAddress,
+            701 => CudaError::LaunchOutOfResources,
+            702 => CudaError::LaunchTimeout,
+            703 => CudaError::LaunchIncompatibleTexturing,
+            704 => CudaError::PeerAccessAlreadyEnabled,
+            705 => CudaError::PeerAccessNotEnabled,
+            708 => CudaError::PrimaryContextActive,
+            709 => CudaError::ContextIsDestroyed,
+            710 => CudaError::AssertError,
+            711 => CudaError::TooManyPeers,
+            712 => CudaError::HostMemoryAlreadyRegistered,
+            713 => CudaError::HostMemoryNotRegistered,
+            714 => CudaError::HardwareStackError,
+            715 => CudaError::IllegalInstruction,
+            716 => CudaError::MisalignedAddress,
+            717 => CudaError::InvalidAddressSpace,
+            718 => CudaError::InvalidProgramCounter,
+            719 => CudaError::LaunchFailed,
+            720 => CudaError::CooperativeLaunchTooLarge,
+            800 => CudaError::NotPermitted,
+            801 => CudaError::NotSupported,
+            802 => CudaError::SystemNotReady,
+            803 => CudaError::SystemDriverMismatch,
+            804 => CudaError::CompatNotSupportedOnDevice,
+            805 => CudaError::MpsConnectionFailed,
+            806 => CudaError::MpsRpcFailed,
+            807 => CudaError::MpsServerNotReady,
+            808 => CudaError::MpsMaxClientsReached,
+            809 => CudaError::MpsMaxConnectionsReached,
+            810 => CudaError::MpsClientTerminated,
+            811 => CudaError::CdpNotSupported,
+            812 => CudaError::CdpVersionMismatch,
+            900 => CudaError::StreamCaptureUnsupported,
+            901 => CudaError::StreamCaptureInvalid,
+            902 => CudaError::StreamCaptureMerge,
+            903 => CudaError::StreamCaptureUnmatched,
+            904 => CudaError::StreamCaptureUnjoined,
+            905 => CudaError::StreamCaptureIsolated,
+            906 => CudaError::StreamCaptureImplicit,
+            907 => CudaError::CapturedEvent,
+            908 => CudaError::StreamCaptureWrongThread,
+            909 => CudaError::Timeout,
+            910 => CudaError::GraphExecUpdateFailure,
+            911 => CudaError::ExternalDevice,
+            912 => CudaError::InvalidClusterSize,
+            913 => CudaError::FunctionNotLoaded,
+            914 => CudaError::InvalidResourceType,
+            915 => CudaError::InvalidResourceConfiguration,
+            999 => CudaError::UnknownError,
+            100_100 => CudaError::InvalidMemoryAllocation,
+            100_101 => CudaError::OptixError,
+            _ => return None,
+        })
+    }
+
+    /// Converts a raw driver status code (as returned by `cust_raw`'s FFI bindings, e.g. from
+    /// calling `cuMemcpyHtoD_v2` directly) into a [`CudaResult`].
+    ///
+    /// Provided for callers that only have the raw `u32` status code on hand and want cust's
+    /// error type without reimplementing the mapping themselves. Codes this table does not
+    /// recognize (e.g. from a newer driver than these bindings cover) are reported as
+    /// [`CudaError::UnknownError`] rather than silently dropped; use `TryFrom<u32>` instead if
+    /// you need to tell an unrecognized code apart from a known one.
+    ///
+    /// This is a pure conversion: unlike [`ToResult::to_result`], it does not update the
+    /// outstanding-error tracker in [`crate::context`], since a status from a driver call this
+    /// crate did not make may not even pertain to a context cust knows about.
+    pub fn from_raw(code: u32) -> CudaResult<()> {
+        if code == 0 {
+            Ok(())
+        } else {
+            Err(CudaError::from_u32(code).unwrap_or(CudaError::UnknownError))
+        }
+    }
+}
+
+impl TryFrom<u32> for CudaError {
+    type Error = u32;
+
+    /// Maps a raw driver status code to the matching [`CudaError`] variant.
+    ///
+    /// Returns `Err(code)` for `CUDA_SUCCESS` (there is no `CudaError` variant representing
+    /// success) and for any code this table does not recognize, so callers can tell "no error"
+    /// and "unmappable code" apart from a known error -- unlike [`CudaError::from_raw`], which
+    /// collapses unrecognized codes into [`CudaError::UnknownError`] for convenience.
+    fn try_from(code: u32) -> Result<Self, Self::Error> {
+        CudaError::from_u32(code).ok_or(code)
+    }
+}
+
+impl CudaError {
+    /// Returns a bundled, pure-Rust description of this error.
+    ///
+    /// Unlike `cuGetErrorString`, this never requires the driver to be initialized and never
+    /// fails, so it is always available for [`Display`](fmt::Display) to fall back on -- in
+    /// particular for `NotInitialized` and `Deinitialized`, which are exactly the cases where
+    /// asking the driver for a string would itself fail.
+    fn static_message(&self) -> &'static str {
+        match self {
+            CudaError::InvalidValue => "one or more of the parameters passed to the API call is not within an acceptable range of values",
+            CudaError::OutOfMemory => "the API call failed because it was unable to allocate enough memory to perform the requested operation",
+            CudaError::NotInitialized => "the CUDA driver has not been initialized with `cust::init`",
+            CudaError::Deinitialized => "the CUDA driver is in the process of shutting down",
+            CudaError::ProfilerDisabled => "profiling APIs are disabled because the application is running with external profiling tools enabled",
+            CudaError::ProfilerNotInitialized => "a profiling API was called while the profiler was not initialized",
+            CudaError::ProfilerAlreadyStarted => "the profiler was already started when `cuProfilerStart` was called",
+            CudaError::ProfilerAlreadyStopped => "the profiler was already stopped when `cuProfilerStop` was called",
+            CudaError::StubLibrary => "the CUDA driver that the application has loaded is a stub library",
+            CudaError::DeviceUnavailable => "requested device is unavailable at this time, often because it is exclusively used by another process",
+            CudaError::NoDevice => "no CUDA-capable devices were detected by the installed CUDA driver",
+            CudaError::InvalidDevice => "the device ordinal supplied does not correspond to a valid CUDA device",
+            CudaError::DeviceNotLicensed => "the device is not licensed for use",
+            CudaError::InvalidImage => "the device kernel image is invalid",
+            CudaError::InvalidContext => "there is no context bound to the current thread, or the context passed in is not a valid handle",
+            CudaError::ContextAlreadyCurrent => "the context being supplied as a parameter is already the active context",
+            CudaError::MapFailed => "a map or register operation has failed",
+            CudaError::UnmapFailed => "an unmap or unregister operation has failed",
+            CudaError::ArrayIsMapped => "the specified array is currently mapped and must be unmapped before it can be freed",
+            CudaError::AlreadyMapped => "the resource is already mapped",
+            CudaError::NoBinaryForGpu => "there is no kernel image available that is suitable for the device",
+            CudaError::AlreadyAcquired => "the resource has already been acquired",
+            CudaError::NotMapped => "the resource is not mapped",
+            CudaError::NotMappedAsArray => "the resource is mapped, but not as an array",
+            CudaError::NotMappedAsPointer => "the resource is mapped, but not as a pointer",
+            CudaError::EccUncorrectable => "an uncorrectable ECC error was detected during execution",
+            CudaError::UnsupportedLimit => "the requested limit is not supported by the device",
+            CudaError::ContextAlreadyInUse => "the context is already in use by another thread",
+            CudaError::PeerAccessUnsupported => "peer access is not supported across the given devices",
+            CudaError::InvalidPtx => "a PTX JIT compilation failed",
+            CudaError::InvalidGraphicsContext => "an error was detected while operating on a graphics context",
+            CudaError::NvlinkUncorrectable => "an uncorrectable NVLink error was detected during execution",
+            CudaError::JitCompilerNotFound => "the PTX JIT compiler library was not found",
+            CudaError::UnsupportedPtxVersion => "the provided PTX was compiled with an unsupported toolchain",
+            CudaError::JitCompilationDisabled => "PTX JIT compilation has been disabled",
+            CudaError::UnsupportedExecAffinity => "the provided execution affinity is not supported by the device",
+            CudaError::UnsupportedDevSideSync => "the provided device-side synchronization is not supported by the device",
+            CudaError::InvalidSource => "the device kernel source is invalid",
+            CudaError::FileNotFound => "the file specified was not found",
+            CudaError::SharedObjectSymbolNotFound => "a link to a shared object failed to resolve",
+            CudaError::SharedObjectInitFailed => "initialization of a shared object failed",
+            CudaError::OperatingSystemError => "an OS call failed",
+            CudaError::InvalidHandle => "a resource handle passed to the API call was not valid",
+            CudaError::IllegalState => "a resource required by the API call is not in a valid state to perform the requested operation",
+            CudaError::LossyQuery => "an attempt was made to query a numeric value that cannot be represented accurately in the requested type",
+            CudaError::NotFound => "a named symbol was not found; usually the name is misspelled or the wrong device or source is used",
+            CudaError::NotReady => "asynchronous operations issued previously have not completed yet",
+            CudaError::IllegalAddress => "the device encountered a load or store instruction on an invalid address; the context is corrupted and cannot be used further",
+            CudaError::LaunchOutOfResources => "the launch used too many resources for the device, such as registers or shared memory",
+            CudaError::LaunchTimeout => "the device kernel took too long to execute",
+            CudaError::LaunchIncompatibleTexturing => "a launch did not occur because the texture or surface references it used were bound to textures or surfaces that did not support the given launch",
+            CudaError::PeerAccessAlreadyEnabled => "peer access is already enabled between the given devices",
+            CudaError::PeerAccessNotEnabled => "peer access has not yet been enabled between the given devices",
+            CudaError::PrimaryContextActive => "the primary context for the device has already been initialized",
+            CudaError::ContextIsDestroyed => "the context current to the calling thread has been destroyed, or is a primary context that has not been initialized",
+            CudaError::AssertError => "a device-side assertion triggered, aborting the kernel; the context is corrupted and cannot be used further",
+            CudaError::TooManyPeers => "adding this peer context would exceed the maximum number of peer connections",
+            CudaError::HostMemoryAlreadyRegistered => "the memory range passed to `cuMemHostRegister` has already been registered",
+            CudaError::HostMemoryNotRegistered => "the pointer passed to `cuMemHostUnregister` does not correspond to any registered memory region",
+            CudaError::HardwareStackError => "the device detected a stack error, such as stack overflow or underflow; the context is corrupted and cannot be used further",
+            CudaError::IllegalInstruction => "the device encountered an illegal instruction; the context is corrupted and cannot be used further",
+            CudaError::MisalignedAddress => "the device encountered a load or store instruction on a memory address that is not aligned; the context is corrupted and cannot be used further",
+            CudaError::InvalidAddressSpace => "the device encountered an instruction referencing an address space not permitted there; the context is corrupted and cannot be used further",
+            CudaError::InvalidProgramCounter => "the device encountered an invalid program counter; the context is corrupted and cannot be used further",
+            CudaError::LaunchFailed => "an exception occurred on the device while executing a kernel; the context is corrupted and cannot be used further",
+            CudaError::CooperativeLaunchTooLarge => "the number of blocks launched exceeds the maximum allowed for cooperative launch on this device with the given configuration",
+            CudaError::NotPermitted => "the attempted operation is not permitted",
+            CudaError::NotSupported => "the attempted operation is not supported on the current system or device",
+            CudaError::SystemNotReady => "the system is not yet ready to start any CUDA work",
+            CudaError::SystemDriverMismatch => "the installed NVIDIA driver is older than the CUDA runtime library; they must be updated together",
+            CudaError::CompatNotSupportedOnDevice => "the system was upgraded to run with forward compatibility, but the visible hardware does not support it",
+            CudaError::MpsConnectionFailed => "the MPS client failed to connect to the MPS control daemon or the MPS server",
+            CudaError::MpsRpcFailed => "the remote procedure call between the MPS server and the MPS client failed",
+            CudaError::MpsServerNotReady => "the MPS server is not ready to accept new MPS client requests",
+            CudaError::MpsMaxClientsReached => "the hardware resources required to support MPS clients have been exhausted",
+            CudaError::MpsMaxConnectionsReached => "the hardware resources required to support connections to MPS clients have been exhausted",
+            CudaError::MpsClientTerminated => "the MPS client has been terminated by the server",
+            CudaError::CdpNotSupported => "the module is not supported for use with CUDA Dynamic Parallelism",
+            CudaError::CdpVersionMismatch => "a CDP call occurred between two modules with incompatible CDP versions",
+            CudaError::StreamCaptureUnsupported => "the operation is not permitted while a stream is capturing",
+            CudaError::StreamCaptureInvalid => "the current capture sequence on the stream has been invalidated and must be terminated",
+            CudaError::StreamCaptureMerge => "the capture sequence attempted to merge two independent capture sequences",
+            CudaError::StreamCaptureUnmatched => "the capture was not initiated in this stream",
+            CudaError::StreamCaptureUnjoined => "the capture sequence contains a fork that was not joined to the primary stream",
+            CudaError::StreamCaptureIsolated => "a dependency would have been created which crosses the capture sequence boundary",
+            CudaError::StreamCaptureImplicit => "the operation would have made the legacy stream dependent on a capturing stream",
+            CudaError::CapturedEvent => "the operation is not permitted on an event which was last recorded in a capturing stream",
+            CudaError::StreamCaptureWrongThread => "a stream capture sequence was not initiated with the `CU_STREAM_CAPTURE_MODE_RELAXED` mode and a different thread must join or end it",
+            CudaError::Timeout => "the timeout specified for the wait operation has lapsed",
+            CudaError::GraphExecUpdateFailure => "the graph update was not performed because it included changes incompatible with instantiate-and-update",
+            CudaError::ExternalDevice => "a device involved in the operation is not a supported external device",
+            CudaError::InvalidClusterSize => "the given cluster size is not supported on this device",
+            CudaError::FunctionNotLoaded => "the function handle provided is not loaded; call `cuModuleGetFunction` again",
+            CudaError::InvalidResourceType => "the resource type provided by the operation is invalid",
+            CudaError::InvalidResourceConfiguration => "the resource configuration provided by the operation is invalid",
+            CudaError::UnknownError => "an unknown internal error has occurred",
+            CudaError::InvalidMemoryAllocation => "invalid memory allocation",
+            CudaError::OptixError => "an OptiX error occurred",
+        }
+    }
+
+    /// Returns the canonical driver symbol for this error, e.g. `"CUDA_ERROR_ILLEGAL_ADDRESS"`.
+    fn static_name(&self) -> &'static str {
+        match self {
+            CudaError::InvalidValue => "CUDA_ERROR_INVALID_VALUE",
+            CudaError::OutOfMemory => "CUDA_ERROR_OUT_OF_MEMORY",
+            CudaError::NotInitialized => "CUDA_ERROR_NOT_INITIALIZED",
+            CudaError::Deinitialized => "CUDA_ERROR_DEINITIALIZED",
+            CudaError::ProfilerDisabled => "CUDA_ERROR_PROFILER_DISABLED",
+            CudaError::ProfilerNotInitialized => "CUDA_ERROR_PROFILER_NOT_INITIALIZED",
+            CudaError::ProfilerAlreadyStarted => "CUDA_ERROR_PROFILER_ALREADY_STARTED",
+            CudaError::ProfilerAlreadyStopped => "CUDA_ERROR_PROFILER_ALREADY_STOPPED",
+            CudaError::StubLibrary => "CUDA_ERROR_STUB_LIBRARY",
+            CudaError::DeviceUnavailable => "CUDA_ERROR_DEVICE_UNAVAILABLE",
+            CudaError::NoDevice => "CUDA_ERROR_NO_DEVICE",
+            CudaError::InvalidDevice => "CUDA_ERROR_INVALID_DEVICE",
+            CudaError::DeviceNotLicensed => "CUDA_ERROR_DEVICE_NOT_LICENSED",
+            CudaError::InvalidImage => "CUDA_ERROR_INVALID_IMAGE",
+            CudaError::InvalidContext => "CUDA_ERROR_INVALID_CONTEXT",
+            CudaError::ContextAlreadyCurrent => "CUDA_ERROR_CONTEXT_ALREADY_CURRENT",
+            CudaError::MapFailed => "CUDA_ERROR_MAP_FAILED",
+            CudaError::UnmapFailed => "CUDA_ERROR_UNMAP_FAILED",
+            CudaError::ArrayIsMapped => "CUDA_ERROR_ARRAY_IS_MAPPED",
+            CudaError::AlreadyMapped => "CUDA_ERROR_ALREADY_MAPPED",
+            CudaError::NoBinaryForGpu => "CUDA_ERROR_NO_BINARY_FOR_GPU",
+            CudaError::AlreadyAcquired => "CUDA_ERROR_ALREADY_ACQUIRED",
+            CudaError::NotMapped => "CUDA_ERROR_NOT_MAPPED",
+            CudaError::NotMappedAsArray => "CUDA_ERROR_NOT_MAPPED_AS_ARRAY",
+            CudaError::NotMappedAsPointer => "CUDA_ERROR_NOT_MAPPED_AS_POINTER",
+            CudaError::EccUncorrectable => "CUDA_ERROR_ECC_UNCORRECTABLE",
+            CudaError::UnsupportedLimit => "CUDA_ERROR_UNSUPPORTED_LIMIT",
+            CudaError::ContextAlreadyInUse => "CUDA_ERROR_CONTEXT_ALREADY_IN_USE",
+            CudaError::PeerAccessUnsupported => "CUDA_ERROR_PEER_ACCESS_UNSUPPORTED",
+            CudaError::InvalidPtx => "CUDA_ERROR_INVALID_PTX",
+            CudaError::InvalidGraphicsContext => "CUDA_ERROR_INVALID_GRAPHICS_CONTEXT",
+            CudaError::NvlinkUncorrectable => "CUDA_ERROR_NVLINK_UNCORRECTABLE",
+            CudaError::JitCompilerNotFound => "CUDA_ERROR_JIT_COMPILER_NOT_FOUND",
+            CudaError::UnsupportedPtxVersion => "CUDA_ERROR_UNSUPPORTED_PTX_VERSION",
+            CudaError::JitCompilationDisabled => "CUDA_ERROR_JIT_COMPILATION_DISABLED",
+            CudaError::UnsupportedExecAffinity => "CUDA_ERROR_UNSUPPORTED_EXEC_AFFINITY",
+            CudaError::UnsupportedDevSideSync => "CUDA_ERROR_UNSUPPORTED_DEVSIDE_SYNC",
+            CudaError::InvalidSource => "CUDA_ERROR_INVALID_SOURCE",
+            CudaError::FileNotFound => "CUDA_ERROR_FILE_NOT_FOUND",
+            CudaError::SharedObjectSymbolNotFound => "CUDA_ERROR_SHARED_OBJECT_SYMBOL_NOT_FOUND",
+            CudaError::SharedObjectInitFailed => "CUDA_ERROR_SHARED_OBJECT_INIT_FAILED",
+            CudaError::OperatingSystemError => "CUDA_ERROR_OPERATING_SYSTEM",
+            CudaError::InvalidHandle => "CUDA_ERROR_INVALID_HANDLE",
+            CudaError::IllegalState => "CUDA_ERROR_ILLEGAL_STATE",
+            CudaError::LossyQuery => "CUDA_ERROR_LOSSY_QUERY",
+            CudaError::NotFound => "CUDA_ERROR_NOT_FOUND",
+            CudaError::NotReady => "CUDA_ERROR_NOT_READY",
+            CudaError::IllegalAddress => "CUDA_ERROR_ILLEGAL_ADDRESS",
+            CudaError::LaunchOutOfResources => "CUDA_ERROR_LAUNCH_OUT_OF_RESOURCES",
+            CudaError::LaunchTimeout => "CUDA_ERROR_LAUNCH_TIMEOUT",
+            CudaError::LaunchIncompatibleTexturing => "CUDA_ERROR_LAUNCH_INCOMPATIBLE_TEXTURING",
+            CudaError::PeerAccessAlreadyEnabled => "CUDA_ERROR_PEER_ACCESS_ALREADY_ENABLED",
+            CudaError::PeerAccessNotEnabled => "CUDA_ERROR_PEER_ACCESS_NOT_ENABLED",
+            CudaError::PrimaryContextActive => "CUDA_ERROR_PRIMARY_CONTEXT_ACTIVE",
+            CudaError::ContextIsDestroyed => "CUDA_ERROR_CONTEXT_IS_DESTROYED",
+            CudaError::AssertError => "CUDA_ERROR_ASSERT",
+            CudaError::TooManyPeers => "CUDA_ERROR_TOO_MANY_PEERS",
+            CudaError::HostMemoryAlreadyRegistered => "CUDA_ERROR_HOST_MEMORY_ALREADY_REGISTERED",
+            CudaError::HostMemoryNotRegistered => "CUDA_ERROR_HOST_MEMORY_NOT_REGISTERED",
+            CudaError::HardwareStackError => "CUDA_ERROR_HARDWARE_STACK_ERROR",
+            CudaError::IllegalInstruction => "CUDA_ERROR_ILLEGAL_INSTRUCTION",
+            CudaError::MisalignedAddress => "CUDA_ERROR_MISALIGNED_ADDRESS",
+            CudaError::InvalidAddressSpace => "CUDA_ERROR_INVALID_ADDRESS_SPACE",
+            CudaError::InvalidProgramCounter => "CUDA_ERROR_INVALID_PC",
+            CudaError::LaunchFailed => "CUDA_ERROR_LAUNCH_FAILED",
+            CudaError::CooperativeLaunchTooLarge => "CUDA_ERROR_COOPERATIVE_LAUNCH_TOO_LARGE",
+            CudaError::NotPermitted => "CUDA_ERROR_NOT_PERMITTED",
+            CudaError::NotSupported => "CUDA_ERROR_NOT_SUPPORTED",
+            CudaError::SystemNotReady => "CUDA_ERROR_SYSTEM_NOT_READY",
+            CudaError::SystemDriverMismatch => "CUDA_ERROR_SYSTEM_DRIVER_MISMATCH",
+            CudaError::CompatNotSupportedOnDevice => "CUDA_ERROR_COMPAT_NOT_SUPPORTED_ON_DEVICE",
+            CudaError::MpsConnectionFailed => "CUDA_ERROR_MPS_CONNECTION_FAILED",
+            CudaError::MpsRpcFailed => "CUDA_ERROR_MPS_RPC_FAILURE",
+            CudaError::MpsServerNotReady => "CUDA_ERROR_MPS_SERVER_NOT_READY",
+            CudaError::MpsMaxClientsReached => "CUDA_ERROR_MPS_MAX_CLIENTS_REACHED",
+            CudaError::MpsMaxConnectionsReached => "CUDA_ERROR_MPS_MAX_CONNECTIONS_REACHED",
+            CudaError::MpsClientTerminated => "CUDA_ERROR_MPS_CLIENT_TERMINATED",
+            CudaError::CdpNotSupported => "CUDA_ERROR_CDP_NOT_SUPPORTED",
+            CudaError::CdpVersionMismatch => "CUDA_ERROR_CDP_VERSION_MISMATCH",
+            CudaError::StreamCaptureUnsupported => "CUDA_ERROR_STREAM_CAPTURE_UNSUPPORTED",
+            CudaError::StreamCaptureInvalid => "CUDA_ERROR_STREAM_CAPTURE_INVALIDATED",
+            CudaError::StreamCaptureMerge => "CUDA_ERROR_STREAM_CAPTURE_MERGE",
+            CudaError::StreamCaptureUnmatched => "CUDA_ERROR_STREAM_CAPTURE_UNMATCHED",
+            CudaError::StreamCaptureUnjoined => "CUDA_ERROR_STREAM_CAPTURE_UNJOINED",
+            CudaError::StreamCaptureIsolated => "CUDA_ERROR_STREAM_CAPTURE_ISOLATION",
+            CudaError::StreamCaptureImplicit => "CUDA_ERROR_STREAM_CAPTURE_IMPLICIT",
+            CudaError::CapturedEvent => "CUDA_ERROR_CAPTURED_EVENT",
+            CudaError::StreamCaptureWrongThread => "CUDA_ERROR_STREAM_CAPTURE_WRONG_THREAD",
+            CudaError::Timeout => "CUDA_ERROR_TIMEOUT",
+            CudaError::GraphExecUpdateFailure => "CUDA_ERROR_GRAPH_EXEC_UPDATE_FAILURE",
+            CudaError::ExternalDevice => "CUDA_ERROR_EXTERNAL_DEVICE",
+            CudaError::InvalidClusterSize => "CUDA_ERROR_INVALID_CLUSTER_SIZE",
+            CudaError::FunctionNotLoaded => "CUDA_ERROR_FUNCTION_NOT_LOADED",
+            CudaError::InvalidResourceType => "CUDA_ERROR_INVALID_RESOURCE_TYPE",
+            CudaError::InvalidResourceConfiguration => "CUDA_ERROR_INVALID_RESOURCE_CONFIGURATION",
+            CudaError::UnknownError => "CUDA_ERROR_UNKNOWN",
+            CudaError::InvalidMemoryAllocation => "CUST_ERROR_INVALID_MEMORY_ALLOCATION",
+            CudaError::OptixError => "CUST_ERROR_OPTIX_ERROR",
+        }
+    }
+
+    /// Returns the canonical symbolic identifier for this error, e.g.
+    /// `"CUDA_ERROR_ILLEGAL_ADDRESS"`.
+    ///
+    /// Backed by `cuGetErrorName` when the driver is initialized and recognizes the error;
+    /// falls back to the bundled static table otherwise, so the symbol is always available
+    /// even before [`cust::init`](crate) has run.
+    pub fn name(&self) -> &'static str {
+        let value = *self as u32;
+        if value <= 999 {
+            let mut ptr: *const c_char = ptr::null();
+            let got_name = unsafe {
+                to_cuda_result(cuda::cuGetErrorName(
+                    mem::transmute(value),
+                    &mut ptr as *mut *const c_char,
+                ))
+                .is_ok()
+                    && !ptr.is_null()
+            };
+            if got_name {
+                // SAFETY: `cuGetErrorName` returns a pointer into a static string table owned
+                // by the driver, valid for the lifetime of the process, so extending the
+                // borrow to `'static` reflects its actual lifetime.
+                if let Ok(name) = unsafe { CStr::from_ptr(ptr) }.to_str() {
+                    return unsafe { mem::transmute::<&str, &'static str>(name) };
+                }
+            }
+        }
+        self.static_name()
+    }
+}
+
 impl fmt::Display for CudaError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match *self {
-            CudaError::InvalidMemoryAllocation => write!(f, "Invalid memory allocation"),
-            CudaError::OptixError => write!(f, "OptiX error"),
-            other if (other as u32) <= 999 => {
-                let value = other as u32;
-                let mut ptr: *const c_char = ptr::null();
-                unsafe {
-                    cuda::cuGetErrorString(mem::transmute(value), &mut ptr as *mut *const c_char)
-                        .to_result()
-                        .map_err(|_| fmt::Error)?;
-                    let cstr = CStr::from_ptr(ptr);
-                    write!(f, "{:?}", cstr)
+        let value = *self as u32;
+        if value <= 999 {
+            let mut ptr: *const c_char = ptr::null();
+            let got_message = unsafe {
+                to_cuda_result(cuda::cuGetErrorString(
+                    mem::transmute(value),
+                    &mut ptr as *mut *const c_char,
+                ))
+                .is_ok()
+                    && !ptr.is_null()
+            };
+            if got_message {
+                if let Ok(message) = unsafe { CStr::from_ptr(ptr) }.to_str() {
+                    return write!(f, "{}", message);
                 }
             }
-            // This shouldn't happen
-            _ => write!(f, "Unknown error"),
         }
+        write!(f, "{}", self.static_message())
     }
 }
 impl Error for CudaError {}
@@ -158,7 +587,23 @@ pub(crate) trait ToResult {
 }
 impl ToResult for cudaError_enum {
     fn to_result(self) -> CudaResult<()> {
-        match self {
+        let result = to_cuda_result(self);
+        if let Err(err) = result {
+            crate::context::record_error(err);
+        }
+        result
+    }
+}
+
+/// Maps a raw driver status code to a [`CudaResult`], without touching the outstanding-error
+/// state tracked by the [`crate::context`] module.
+///
+/// `cudaError_enum` is defined in `cust_raw`, so this has to be a free function rather than an
+/// inherent `impl` on it. [`ToResult::to_result`] is the version callers within the crate should
+/// use; this backs it and is kept separate so the raw mapping itself can be reused without the
+/// side effect.
+fn to_cuda_result(code: cudaError_enum) -> CudaResult<()> {
+    match code {
             cudaError_enum::CUDA_SUCCESS => Ok(()),
             cudaError_enum::CUDA_ERROR_INVALID_VALUE => Err(CudaError::InvalidValue),
             cudaError_enum::CUDA_ERROR_OUT_OF_MEMORY => Err(CudaError::OutOfMemory),
@@ -0,0 +1,8 @@
+//! cust: safe bindings to the CUDA driver API.
+
+pub mod context;
+pub mod error;
+pub mod memory;
+
+pub use error::{CudaError, CudaResult, DropResult};
+pub use memory::{DeviceBox, DeviceBuffer, RetryDecision};
@@ -0,0 +1,146 @@
+//! Plain device-memory buffers and boxes.
+
+use crate::error::{CudaError, CudaResult, ToResult};
+use crate::memory::{alloc_with_retry, never_retry, RetryDecision};
+use crate::sys::{self as cuda, CUdeviceptr};
+use cust_core::DeviceCopy;
+use std::marker::PhantomData;
+use std::mem;
+
+/// A buffer of device memory that stores `T`s, but does not provide any access to the data
+/// from the host.
+#[derive(Debug)]
+pub struct DeviceBuffer<T> {
+    ptr: CUdeviceptr,
+    len: usize,
+    _marker: PhantomData<T>,
+}
+
+impl<T: DeviceCopy> DeviceBuffer<T> {
+    /// Allocates device memory for `len` elements of `T`, without initializing it.
+    ///
+    /// Never retries on [`CudaError::OutOfMemory`]; use
+    /// [`uninitialized_with_retry`](Self::uninitialized_with_retry) to install a retry hook.
+    ///
+    /// # Safety
+    ///
+    /// The memory is not initialized. Reading from it before writing is undefined behavior.
+    pub unsafe fn uninitialized(len: usize) -> CudaResult<Self> {
+        Self::uninitialized_with_retry(len, never_retry)
+    }
+
+    /// Allocates device memory for `len` elements of `T`, without initializing it.
+    ///
+    /// If the allocation fails with [`CudaError::OutOfMemory`], `on_oom` is consulted with the
+    /// number of bytes requested and the attempt number; see [`alloc_with_retry`] for the
+    /// retry semantics.
+    ///
+    /// # Safety
+    ///
+    /// The memory is not initialized. Reading from it before writing is undefined behavior.
+    pub unsafe fn uninitialized_with_retry(
+        len: usize,
+        on_oom: impl FnMut(usize, usize) -> RetryDecision,
+    ) -> CudaResult<Self> {
+        let bytes = len
+            .checked_mul(mem::size_of::<T>())
+            .ok_or(CudaError::InvalidMemoryAllocation)?;
+        let ptr = if bytes == 0 {
+            0
+        } else {
+            alloc_with_retry(bytes, || Self::alloc(bytes), on_oom)?
+        };
+        Ok(Self {
+            ptr,
+            len,
+            _marker: PhantomData,
+        })
+    }
+
+    unsafe fn alloc(bytes: usize) -> CudaResult<CUdeviceptr> {
+        let mut ptr: CUdeviceptr = 0;
+        cuda::cuMemAlloc_v2(&mut ptr, bytes).to_result()?;
+        Ok(ptr)
+    }
+
+    /// Returns the number of elements this buffer holds.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if this buffer holds no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl<T> Drop for DeviceBuffer<T> {
+    fn drop(&mut self) {
+        if self.ptr != 0 {
+            unsafe {
+                let _ = cuda::cuMemFree_v2(self.ptr).to_result();
+            }
+        }
+    }
+}
+
+/// A single value stored in device memory, but not directly accessible from the host.
+#[derive(Debug)]
+pub struct DeviceBox<T> {
+    ptr: CUdeviceptr,
+    _marker: PhantomData<T>,
+}
+
+impl<T: DeviceCopy> DeviceBox<T> {
+    /// Allocates device memory to hold a `T`, without initializing it.
+    ///
+    /// Never retries on [`CudaError::OutOfMemory`]; use
+    /// [`uninitialized_with_retry`](Self::uninitialized_with_retry) to install a retry hook.
+    ///
+    /// # Safety
+    ///
+    /// The memory is not initialized. Reading from it before writing is undefined behavior.
+    pub unsafe fn uninitialized() -> CudaResult<Self> {
+        Self::uninitialized_with_retry(never_retry)
+    }
+
+    /// Allocates device memory to hold a `T`, without initializing it.
+    ///
+    /// If the allocation fails with [`CudaError::OutOfMemory`], `on_oom` is consulted with the
+    /// number of bytes requested and the attempt number; see [`alloc_with_retry`] for the
+    /// retry semantics.
+    ///
+    /// # Safety
+    ///
+    /// The memory is not initialized. Reading from it before writing is undefined behavior.
+    pub unsafe fn uninitialized_with_retry(
+        on_oom: impl FnMut(usize, usize) -> RetryDecision,
+    ) -> CudaResult<Self> {
+        let bytes = mem::size_of::<T>();
+        let ptr = if bytes == 0 {
+            0
+        } else {
+            alloc_with_retry(bytes, || Self::alloc(bytes), on_oom)?
+        };
+        Ok(Self {
+            ptr,
+            _marker: PhantomData,
+        })
+    }
+
+    unsafe fn alloc(bytes: usize) -> CudaResult<CUdeviceptr> {
+        let mut ptr: CUdeviceptr = 0;
+        cuda::cuMemAlloc_v2(&mut ptr, bytes).to_result()?;
+        Ok(ptr)
+    }
+}
+
+impl<T> Drop for DeviceBox<T> {
+    fn drop(&mut self) {
+        if self.ptr != 0 {
+            unsafe {
+                let _ = cuda::cuMemFree_v2(self.ptr).to_result();
+            }
+        }
+    }
+}
@@ -0,0 +1,62 @@
+//! Device memory allocation and management.
+//!
+//! Allocations made through this module are the most common way an application will run into
+//! [`CudaError::OutOfMemory`]. Because that error is non-sticky (see
+//! [`CudaError::is_sticky`](crate::error::CudaError::is_sticky)), a caller has a real
+//! opportunity to recover from it: free cached buffers or trim a pool, then retry the
+//! allocation. [`alloc_with_retry`] and [`RetryDecision`] build that loop so [`DeviceBuffer`]
+//! and [`DeviceBox`] don't have to hard-fail on the first rejected `cuMemAlloc`.
+
+mod device;
+
+pub use device::{DeviceBox, DeviceBuffer};
+
+use crate::error::{CudaError, CudaResult};
+
+/// The number of allocation attempts [`alloc_with_retry`] will make before giving up, even if
+/// the retry callback keeps asking for another attempt. Bounds the loop so a callback that
+/// always returns [`RetryDecision::Retry`] cannot spin forever.
+const MAX_OOM_RETRY_ATTEMPTS: usize = 16;
+
+/// The decision returned by an out-of-memory callback passed to [`alloc_with_retry`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetryDecision {
+    /// Free up memory (e.g. evict a cache or trim a pool) and attempt the allocation again.
+    Retry,
+    /// Give up and propagate the original `OutOfMemory` error.
+    Fail,
+}
+
+/// Runs `alloc`, and whenever it fails with a retryable error (see
+/// [`CudaError::is_retryable`]), consults `on_oom` with the number of bytes originally
+/// requested and the attempt number (starting at `1`) before trying again.
+///
+/// `on_oom` typically frees cached buffers or trims a pool before returning
+/// [`RetryDecision::Retry`]. Returning [`RetryDecision::Fail`], or exhausting
+/// [`MAX_OOM_RETRY_ATTEMPTS`], propagates the original error.
+pub(crate) fn alloc_with_retry<T>(
+    requested_bytes: usize,
+    mut alloc: impl FnMut() -> CudaResult<T>,
+    mut on_oom: impl FnMut(usize, usize) -> RetryDecision,
+) -> CudaResult<T> {
+    let mut attempt = 1;
+    loop {
+        match alloc() {
+            Err(err) if err.is_retryable() && attempt < MAX_OOM_RETRY_ATTEMPTS => {
+                match on_oom(requested_bytes, attempt) {
+                    RetryDecision::Retry => {
+                        attempt += 1;
+                    }
+                    RetryDecision::Fail => return Err(err),
+                }
+            }
+            result => return result,
+        }
+    }
+}
+
+/// An [`alloc_with_retry`] callback that never retries, for allocation paths that have not
+/// opted into a retry hook.
+fn never_retry(_requested_bytes: usize, _attempt: usize) -> RetryDecision {
+    RetryDecision::Fail
+}